@@ -14,6 +14,12 @@ extern "C" {
         map_fn: &Closure<dyn FnMut(JsValue) -> JsValue>,
     ) -> Readable;
 
+    #[wasm_bindgen(js_name = "derived")]
+    pub fn derived_many(
+        stores: &js_sys::Array,
+        map_fn: &Closure<dyn FnMut(JsValue) -> JsValue>,
+    ) -> Readable;
+
     #[wasm_bindgen(method)]
     pub fn set(this: &Writable, value: JsValue);
 
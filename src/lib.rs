@@ -6,11 +6,18 @@
 //! the UI can directly react to changes that happen with
 //! the Rust-WASM world.
 //!
-//! This crate exposes one struct, mainly [`Readable`], which
-//! allows seemless management of readable Svelte stores in JS.
+//! This crate exposes three store structs, [`Readable`], [`Writable`],
+//! and [`SharedReadable`], which allow seemless management of Svelte
+//! stores in JS, plus the [`Subscription`] and [`SuspendGuard`] guards
+//! that [`Readable`] hands out.
 //! Despite it's name, [`Readable`] can be written to from Rust,
 //! but only yields a `Readable` store to JS, making sure that
-//! mutation can only happen within Rust's safety guarantees.
+//! mutation can only happen within Rust's safety guarantees. [`Writable`]
+//! relaxes this so that JS can mutate the store too, with those edits
+//! propagated back into the Rust-owned value. [`SharedReadable`] is
+//! [`Readable`]'s `Clone`-able, multiple-owner sibling, backed by
+//! [`Rc<RefCell<T>>`](std::rc::Rc) instead of relying on there being a
+//! single instance.
 //!
 //! These stores can additionally be annotated with Typescript types
 //! to provide better safety from the JS side. To see how, check out
@@ -21,15 +28,23 @@
 mod bindings;
 
 use std::{
-    cell::UnsafeCell,
+    cell::{Ref, RefCell, UnsafeCell},
     fmt,
     ops::{self, Deref},
+    rc::Rc,
 };
 use wasm_bindgen::prelude::*;
 
+#[cfg(target_arch = "wasm32")]
+use std::cell::Cell;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::JsCast;
+
 /// Rust-managed `Readable` Svelte store.
 pub struct Readable<T> {
-    value: UnsafeCell<T>,
+    // Boxed so the address stays stable even if `Self` is moved, since
+    // `Readable::subscribe`'s closure holds a raw pointer into it.
+    value: Box<UnsafeCell<T>>,
     #[cfg(target_arch = "wasm32")]
     writable_store: bindings::Writable,
     #[cfg(target_arch = "wasm32")]
@@ -37,7 +52,17 @@ pub struct Readable<T> {
     #[allow(clippy::type_complexity)]
     #[cfg(target_arch = "wasm32")]
     mapped_set_fn: Box<dyn FnMut(&T) -> JsValue>,
+    #[cfg(target_arch = "wasm32")]
     _derived_store_map_fn: Closure<dyn FnMut(JsValue) -> JsValue>,
+    /// Set while a [`SuspendGuard`] is alive; `set`/`set_with` record
+    /// the value as dirty instead of notifying JS immediately.
+    #[cfg(target_arch = "wasm32")]
+    suspended: Cell<bool>,
+    /// Set by `set`/`set_with` when they run while `suspended` and
+    /// skip their own JS notification; the [`SuspendGuard`] checks this
+    /// on drop to decide whether it still owes a flush.
+    #[cfg(target_arch = "wasm32")]
+    dirty: Cell<bool>,
 }
 
 impl<T> fmt::Debug for Readable<T>
@@ -87,7 +112,7 @@ impl<T> ops::Deref for Readable<T> {
 
 impl<T: 'static> Readable<T> {
     #[allow(unused_variables)]
-    fn init_store<F>(initial_value: UnsafeCell<T>, mapping_fn: F) -> Self
+    fn init_store<F>(initial_value: Box<UnsafeCell<T>>, mapping_fn: F) -> Self
     where
         F: FnMut(&T) -> JsValue + 'static,
     {
@@ -111,6 +136,8 @@ impl<T: 'static> Readable<T> {
                 derived_store,
                 mapped_set_fn,
                 _derived_store_map_fn: derived_store_map_fn,
+                suspended: Cell::new(false),
+                dirty: Cell::new(false),
             }
         };
 
@@ -158,7 +185,10 @@ impl<T: 'static> Readable<T> {
     where
         T: Clone + Into<JsValue>,
     {
-        Self::init_store(UnsafeCell::new(initial_value), |v| v.clone().into())
+        Self::init_store(
+            Box::new(UnsafeCell::new(initial_value)),
+            |v| v.clone().into(),
+        )
     }
 
     /// Creates a new `Readable` Svelte store which calls its mapping fn each
@@ -190,11 +220,22 @@ impl<T: 'static> Readable<T> {
     where
         F: FnMut(&T) -> JsValue + 'static,
     {
-        Self::init_store(UnsafeCell::new(initial_value), mapping_fn)
+        Self::init_store(Box::new(UnsafeCell::new(initial_value)), mapping_fn)
     }
 
     /// Sets the value of the store, notifying all store
     /// listeners the value has changed.
+    ///
+    /// Has no useful effect on a `Readable` built by
+    /// [`Readable::derived`]/[`Readable::derived2`]/
+    /// [`Readable::derived3`]: those stores are driven entirely by
+    /// Svelte's reactivity system, so `new_value` only replaces the
+    /// value read through Rust's [`Deref`] and is written into an
+    /// internal store nothing subscribes to: neither JS nor
+    /// [`Readable::subscribe`] callbacks see it, and the combined value
+    /// Svelte computes from the source stores is unaffected. Only call
+    /// `set`/[`Readable::set_with`] on a `Readable` built by
+    /// [`Readable::new`]/[`Readable::new_mapped`].
     pub fn set(&mut self, new_value: T) {
         // SAFETY:
         // This is safe because this function is the only way to
@@ -206,13 +247,25 @@ impl<T: 'static> Readable<T> {
         *value = new_value;
 
         #[cfg(target_arch = "wasm32")]
-        self.writable_store.set((self.mapped_set_fn)(value));
+        {
+            if self.suspended.get() {
+                self.dirty.set(true);
+            } else {
+                self.writable_store.set((self.mapped_set_fn)(value));
+            }
+        }
     }
 
     /// Calls the provided `f` with a `&mut T`, returning
     /// whatever `f` returns. After this function is called,
     /// the store will be updated and all store listeners will
     /// be notified.
+    ///
+    /// Same caveat as [`Readable::set`]: on a `Readable` built by
+    /// [`Readable::derived`]/[`Readable::derived2`]/
+    /// [`Readable::derived3`], this only changes the value read through
+    /// Rust's [`Deref`] and an internal store nothing subscribes to;
+    /// JS never sees the change.
     pub fn set_with<F, O>(&mut self, f: F) -> O
     where
         F: FnOnce(&mut T) -> O,
@@ -229,12 +282,88 @@ impl<T: 'static> Readable<T> {
 
         #[cfg(target_arch = "wasm32")]
         {
-            self.writable_store.set((self.mapped_set_fn)(value));
+            if self.suspended.get() {
+                self.dirty.set(true);
+            } else {
+                self.writable_store.set((self.mapped_set_fn)(value));
+            }
         }
 
         o
     }
 
+    /// An alias for [`Readable::set_with`] with no behavior of its own —
+    /// `set_with` already fires exactly one JS notification after `f`
+    /// returns, no matter how many fields `f` touches. `batch` exists
+    /// purely so call sites that are about grouping several edits
+    /// together can say so; reach for [`Readable::suspend_notifications`]
+    /// instead if you need to batch across multiple separate
+    /// `set`/`set_with` calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svelte_store::Readable;
+    /// use wasm_bindgen::prelude::*;
+    ///
+    /// let mut store =
+    ///     Readable::new_mapped(vec![1u32, 2u32], |values: &Vec<u32>| {
+    ///         values
+    ///             .iter()
+    ///             .cloned()
+    ///             .map(JsValue::from)
+    ///             .collect::<js_sys::Array>()
+    ///             .into()
+    ///     });
+    ///
+    /// store.batch(|values| {
+    ///     values[0] += 1;
+    ///     values[1] += 1;
+    /// });
+    ///
+    /// assert_eq!(*store, vec![2, 3]);
+    /// ```
+    pub fn batch<F, O>(&mut self, f: F) -> O
+    where
+        F: FnOnce(&mut T) -> O,
+    {
+        self.set_with(f)
+    }
+
+    /// Suspends JS notifications from [`Readable::set`]/
+    /// [`Readable::set_with`] until the returned [`SuspendGuard`] is
+    /// dropped, at which point a single notification is sent if the
+    /// value was changed at all. Useful for silencing the
+    /// per-mutation notification a Rust loop would otherwise trigger
+    /// when performing many small updates.
+    ///
+    /// The guard derefs to `&`/`&mut Readable<T>`, so [`Readable::set`]
+    /// and [`Readable::set_with`] are called through it as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svelte_store::Readable;
+    ///
+    /// let mut store = Readable::new(0u32);
+    ///
+    /// {
+    ///     let mut guard = store.suspend_notifications();
+    ///
+    ///     guard.set(1);
+    ///     guard.set(2);
+    ///     guard.set(3);
+    /// }
+    ///
+    /// assert_eq!(*store, 3);
+    /// ```
+    pub fn suspend_notifications(&mut self) -> SuspendGuard<'_, T> {
+        #[cfg(target_arch = "wasm32")]
+        self.suspended.set(true);
+
+        SuspendGuard { store: self }
+    }
+
     /// Gets the store that can be directly passed to JS and subscribed
     /// to.
     ///
@@ -280,4 +409,837 @@ impl<T: 'static> Readable<T> {
         #[cfg(target_arch = "wasm32")]
         return self.derived_store.clone();
     }
+
+    /// Subscribes to this store's changes from Rust, useful for
+    /// triggering side effects (persistence, logging, downstream
+    /// recomputation) whenever the store is written. The callback is
+    /// invoked with a borrow of the current Rust value directly,
+    /// rather than re-deserializing the notified [`JsValue`].
+    ///
+    /// Multiple concurrent subscriptions are supported. Dropping the
+    /// returned [`Subscription`] unsubscribes the callback.
+    ///
+    /// On targets other than `wasm32`, there is no underlying store to
+    /// subscribe to, so `f` is never called.
+    ///
+    /// # Examples
+    ///
+    /// Subscribing and then moving the `Readable` (e.g. into a struct
+    /// field) is sound: the subscription keeps working because the
+    /// value it reads from lives in a stable heap allocation that
+    /// doesn't move with `Self`.
+    ///
+    /// ```
+    /// use svelte_store::Readable;
+    ///
+    /// struct Holder {
+    ///     store: Readable<u32>,
+    /// }
+    ///
+    /// let store = Readable::new(0u32);
+    /// let _subscription = store.subscribe(|_value| {});
+    ///
+    /// // Moving `store` here must not invalidate the subscription above.
+    /// let mut holder = Holder { store };
+    /// holder.store.set(1);
+    /// ```
+    pub fn subscribe<F>(&self, #[allow(unused_mut)] mut f: F) -> Subscription
+    where
+        F: FnMut(&T) + 'static,
+    {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let value_ptr: *mut T = self.value.get();
+
+            // SAFETY:
+            // `value_ptr` points into the `Box<UnsafeCell<T>>` owned by
+            // this `Readable`, whose heap allocation does not move when
+            // `Self` is moved (e.g. into a `#[wasm_bindgen]` struct
+            // field), so the pointer stays valid for as long as the
+            // `Readable` itself is alive. The closure is only ever
+            // invoked by `derived_store`'s `subscribe`, and dropping the
+            // returned `Subscription` unregisters it.
+            let closure: Closure<dyn FnMut(JsValue)> =
+                Closure::new(move |_: JsValue| {
+                    f(unsafe { &*value_ptr });
+                });
+
+            let unsubscribe = self.derived_store.subscribe(&closure);
+
+            return Subscription {
+                _closure: closure,
+                unsubscribe,
+            };
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = f;
+
+            Subscription {}
+        }
+    }
+
+    /// Creates a new `Readable` derived from multiple source stores of
+    /// the same type, recomputed in Svelte whenever any source changes.
+    /// `combine` is called with the sources' current values, already
+    /// decoded back to `U`, rather than a raw [`js_sys::Array`] the
+    /// caller would otherwise have to index and cast into manually.
+    ///
+    /// For combining stores of different types, see
+    /// [`Readable::derived2`] and [`Readable::derived3`].
+    ///
+    /// Note: unlike [`Readable::new_mapped`], the value read through
+    /// Rust's [`Deref`] reflects `T`'s default value, or whatever
+    /// [`Readable::set`]/[`Readable::set_with`] last wrote; recomputation happens entirely within Svelte's reactivity
+    /// system and is not observed by Rust. Use [`Readable::get_store`]
+    /// from JS to read the live, combined value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svelte_store::Readable;
+    ///
+    /// let a = Readable::new(1.0f64);
+    /// let b = Readable::new(2.0f64);
+    ///
+    /// let sum: Readable<f64> = Readable::derived(&[&a, &b], |values: &[f64]| {
+    ///     values.iter().sum()
+    /// });
+    /// ```
+    pub fn derived<U, F>(sources: &[&Readable<U>], combine: F) -> Self
+    where
+        T: Default + Clone + Into<JsValue>,
+        U: TryFrom<JsValue> + 'static,
+        U::Error: fmt::Debug,
+        F: FnMut(&[U]) -> T + 'static,
+    {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let stores = sources
+                .iter()
+                .map(|source| JsValue::from(source.derived_store.clone()))
+                .collect::<js_sys::Array>();
+
+            let mut combine = combine;
+            let wrapped = move |values: &js_sys::Array| {
+                let values = values
+                    .iter()
+                    .map(|v| {
+                        U::try_from(v).expect(
+                            "JS source store held a value that could \
+                             not be converted back to `U`",
+                        )
+                    })
+                    .collect::<Vec<U>>();
+
+                combine(&values).into()
+            };
+
+            return Self::build_derived(&stores, T::default(), wrapped);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = (sources, combine);
+
+            Self {
+                value: Box::new(UnsafeCell::new(T::default())),
+            }
+        }
+    }
+
+    /// Like [`Readable::derived`], but combines exactly two source
+    /// stores, which may hold different types; `combine` receives `&A`
+    /// and `&B` directly instead of a raw [`js_sys::Array`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svelte_store::Readable;
+    ///
+    /// let a = Readable::new(1.0f64);
+    /// let b = Readable::new("two".to_owned());
+    ///
+    /// let joined: Readable<String> =
+    ///     Readable::derived2(&a, &b, |a: &f64, b: &String| {
+    ///         format!("{a} {b}")
+    ///     });
+    /// ```
+    pub fn derived2<A, B, F>(
+        a: &Readable<A>,
+        b: &Readable<B>,
+        combine: F,
+    ) -> Self
+    where
+        T: Default + Clone + Into<JsValue>,
+        A: TryFrom<JsValue> + 'static,
+        A::Error: fmt::Debug,
+        B: TryFrom<JsValue> + 'static,
+        B::Error: fmt::Debug,
+        F: FnMut(&A, &B) -> T + 'static,
+    {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let stores = [
+                JsValue::from(a.derived_store.clone()),
+                JsValue::from(b.derived_store.clone()),
+            ]
+            .into_iter()
+            .collect::<js_sys::Array>();
+
+            let mut combine = combine;
+            let wrapped = move |values: &js_sys::Array| {
+                let a = A::try_from(values.get(0)).expect(
+                    "JS source store held a value that could not be \
+                     converted back to `A`",
+                );
+                let b = B::try_from(values.get(1)).expect(
+                    "JS source store held a value that could not be \
+                     converted back to `B`",
+                );
+
+                combine(&a, &b).into()
+            };
+
+            return Self::build_derived(&stores, T::default(), wrapped);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = (a, b, combine);
+
+            Self {
+                value: Box::new(UnsafeCell::new(T::default())),
+            }
+        }
+    }
+
+    /// Like [`Readable::derived`], but combines exactly three source
+    /// stores, which may hold different types; `combine` receives `&A`,
+    /// `&B`, and `&C` directly instead of a raw [`js_sys::Array`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svelte_store::Readable;
+    ///
+    /// let a = Readable::new(1.0f64);
+    /// let b = Readable::new(2.0f64);
+    /// let c = Readable::new(3.0f64);
+    ///
+    /// let sum: Readable<f64> = Readable::derived3(
+    ///     &a,
+    ///     &b,
+    ///     &c,
+    ///     |a: &f64, b: &f64, c: &f64| a + b + c,
+    /// );
+    /// ```
+    pub fn derived3<A, B, C, F>(
+        a: &Readable<A>,
+        b: &Readable<B>,
+        c: &Readable<C>,
+        combine: F,
+    ) -> Self
+    where
+        T: Default + Clone + Into<JsValue>,
+        A: TryFrom<JsValue> + 'static,
+        A::Error: fmt::Debug,
+        B: TryFrom<JsValue> + 'static,
+        B::Error: fmt::Debug,
+        C: TryFrom<JsValue> + 'static,
+        C::Error: fmt::Debug,
+        F: FnMut(&A, &B, &C) -> T + 'static,
+    {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let stores = [
+                JsValue::from(a.derived_store.clone()),
+                JsValue::from(b.derived_store.clone()),
+                JsValue::from(c.derived_store.clone()),
+            ]
+            .into_iter()
+            .collect::<js_sys::Array>();
+
+            let mut combine = combine;
+            let wrapped = move |values: &js_sys::Array| {
+                let a = A::try_from(values.get(0)).expect(
+                    "JS source store held a value that could not be \
+                     converted back to `A`",
+                );
+                let b = B::try_from(values.get(1)).expect(
+                    "JS source store held a value that could not be \
+                     converted back to `B`",
+                );
+                let c = C::try_from(values.get(2)).expect(
+                    "JS source store held a value that could not be \
+                     converted back to `C`",
+                );
+
+                combine(&a, &b, &c).into()
+            };
+
+            return Self::build_derived(&stores, T::default(), wrapped);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = (a, b, c, combine);
+
+            Self {
+                value: Box::new(UnsafeCell::new(T::default())),
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn build_derived<F>(stores: &js_sys::Array, initial_value: T, mut combine: F) -> Self
+    where
+        T: Clone + Into<JsValue>,
+        F: FnMut(&js_sys::Array) -> JsValue + 'static,
+    {
+        let value = Box::new(UnsafeCell::new(initial_value));
+
+        let mut mapped_set_fn =
+            Box::new(|v: &T| v.clone().into()) as Box<dyn FnMut(&T) -> JsValue>;
+
+        let writable_store =
+            bindings::writable(mapped_set_fn(unsafe { &*value.get() }));
+
+        let derived_store_map_fn: Closure<dyn FnMut(JsValue) -> JsValue> =
+            Closure::new(move |values: JsValue| {
+                combine(values.unchecked_ref::<js_sys::Array>())
+            });
+
+        let derived_store =
+            bindings::derived_many(stores, &derived_store_map_fn);
+
+        Self {
+            value,
+            writable_store,
+            derived_store,
+            mapped_set_fn,
+            _derived_store_map_fn: derived_store_map_fn,
+            suspended: Cell::new(false),
+            dirty: Cell::new(false),
+        }
+    }
+}
+
+/// RAII guard for a Rust-side subscription registered via
+/// [`Readable::subscribe`]. Dropping it unsubscribes the underlying
+/// Svelte store listener.
+pub struct Subscription {
+    #[cfg(target_arch = "wasm32")]
+    _closure: Closure<dyn FnMut(JsValue)>,
+    #[cfg(target_arch = "wasm32")]
+    unsubscribe: js_sys::Function,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let _ = self.unsubscribe.call0(&JsValue::NULL);
+    }
+}
+
+/// RAII guard returned by [`Readable::suspend_notifications`]. While
+/// held, [`Readable::set`]/[`Readable::set_with`] record the store as
+/// dirty instead of notifying JS; dropping the guard flushes a single
+/// notification if the value changed at all while it was alive.
+pub struct SuspendGuard<'a, T> {
+    store: &'a mut Readable<T>,
+}
+
+impl<'a, T> ops::Deref for SuspendGuard<'a, T> {
+    type Target = Readable<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.store
+    }
+}
+
+impl<'a, T> ops::DerefMut for SuspendGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.store
+    }
+}
+
+impl<'a, T> Drop for SuspendGuard<'a, T> {
+    fn drop(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.store.suspended.set(false);
+
+            if self.store.dirty.replace(false) {
+                // SAFETY: see `Readable::set`; `&mut self.store` gives
+                // us the same exclusive access that method relies on.
+                let value = unsafe { &*self.store.value.get() };
+
+                self.store
+                    .writable_store
+                    .set((self.store.mapped_set_fn)(value));
+            }
+        }
+    }
+}
+
+/// Rust-managed `Writable` Svelte store.
+///
+/// Unlike [`Readable`], edits made from JS via `set`/`update` on the
+/// store returned from [`Writable::get_store`] are propagated back into
+/// the Rust-owned value, so both sides can mutate it.
+pub struct Writable<T> {
+    // Boxed so the address stays stable even if `Self` is moved, since
+    // the wasm32 subscription closure below holds a raw pointer into it.
+    value: Box<UnsafeCell<T>>,
+    #[allow(clippy::type_complexity)]
+    #[cfg(target_arch = "wasm32")]
+    mapped_set_fn: Box<dyn FnMut(&T) -> JsValue>,
+    #[cfg(target_arch = "wasm32")]
+    writable_store: bindings::Writable,
+    #[cfg(target_arch = "wasm32")]
+    updating: Rc<Cell<bool>>,
+    #[cfg(target_arch = "wasm32")]
+    _subscribe_closure: Closure<dyn FnMut(JsValue)>,
+    #[cfg(target_arch = "wasm32")]
+    unsubscribe: js_sys::Function,
+}
+
+impl<T> fmt::Debug for Writable<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Writable").field(self.deref()).finish()
+    }
+}
+
+impl<T> fmt::Display for Writable<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+/// [`Writable`] relies on the fact that only one instance can exist at
+/// a time to provide transparent dereferencing to the inner value, just
+/// like [`Readable`]. See [`Readable`]'s [`Deref`] impl for the
+/// reasoning; the same invariant applies here, with the subscription
+/// callback registered in [`Writable::init_store`] being the other
+/// (JS-originated) writer, guarded by the `updating` flag so it never
+/// overlaps with a Rust-originated [`Writable::set`].
+impl<T> ops::Deref for Writable<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY:
+        // This is safe because `set`/`set_with` are the only
+        // Rust-initiated ways to mutate T, which already require
+        // &mut Self, and the JS-initiated subscription callback is
+        // guarded by `updating` so it returns without writing while a
+        // Rust write is in flight.
+        unsafe { &*self.value.get() }
+    }
+}
+
+impl<T: 'static> Writable<T> {
+    #[allow(unused_variables)]
+    fn init_store<F, G>(
+        initial_value: Box<UnsafeCell<T>>,
+        mapping_fn: F,
+        from_js: G,
+    ) -> Self
+    where
+        F: FnMut(&T) -> JsValue + 'static,
+        G: FnMut(JsValue) -> T + 'static,
+    {
+        #[cfg(target_arch = "wasm32")]
+        let this = {
+            let mut mapped_set_fn =
+                Box::new(mapping_fn) as Box<dyn FnMut(&T) -> JsValue>;
+            let mut from_js = from_js;
+
+            let writable_store = bindings::writable(mapped_set_fn(unsafe {
+                &*initial_value.get()
+            }));
+
+            let updating = Rc::new(Cell::new(false));
+            let updating_in_closure = Rc::clone(&updating);
+
+            let value_ptr: *mut T = initial_value.get();
+
+            // SAFETY:
+            // `value_ptr` points into the `Box<UnsafeCell<T>>` owned by
+            // this `Writable`, whose heap allocation does not move when
+            // `Self` is moved. The closure below is only ever invoked
+            // by `writable_store`'s `subscribe`, which is unsubscribed
+            // before this `Writable` is dropped, so the pointer is
+            // valid for as long as the closure can be called.
+            let subscribe_closure: Closure<dyn FnMut(JsValue)> =
+                Closure::new(move |js_value: JsValue| {
+                    if updating_in_closure.get() {
+                        return;
+                    }
+
+                    let new_value = from_js(js_value);
+
+                    unsafe {
+                        *value_ptr = new_value;
+                    }
+                });
+
+            // Svelte invokes a new subscriber synchronously, with the
+            // current value, as soon as it subscribes. Guard that
+            // initial call with `updating` the same as any other
+            // JS-initiated write, so it can't clobber `initial_value`
+            // with a lossy `from_js(mapped_set_fn(v))` round-trip.
+            updating.set(true);
+            let unsubscribe =
+                writable_store.subscribe(&subscribe_closure);
+            updating.set(false);
+
+            Self {
+                value: initial_value,
+                mapped_set_fn,
+                writable_store,
+                updating,
+                _subscribe_closure: subscribe_closure,
+                unsubscribe,
+            }
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let this = {
+            Self {
+                value: initial_value,
+            }
+        };
+
+        this
+    }
+
+    /// Creates a `Writable` Svelte store.
+    ///
+    /// This function is only implemented for types that can be
+    /// converted to and from [`JsValue`]. If your type does not
+    /// provide these, use [`Writable::new_mapped`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if JS ever writes a value that fails to convert back to
+    /// `T` via [`TryFrom<JsValue>`]. If that's a real possibility for
+    /// your type, use [`Writable::new_mapped`] with a `from_js` that
+    /// handles the error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svelte_store::Writable;
+    ///
+    /// let store = Writable::new(0.0f64);
+    /// ```
+    pub fn new(initial_value: T) -> Self
+    where
+        T: Clone + Into<JsValue> + TryFrom<JsValue>,
+        <T as TryFrom<JsValue>>::Error: fmt::Debug,
+    {
+        Self::init_store(
+            Box::new(UnsafeCell::new(initial_value)),
+            |v| v.clone().into(),
+            |v| {
+                T::try_from(v).expect(
+                    "JS wrote a value that could not be converted back to `T`",
+                )
+            },
+        )
+    }
+
+    /// Creates a new `Writable` Svelte store which calls `mapping_fn`
+    /// each time the store is set from Rust, and `from_js` each time
+    /// JS edits the store, to convert between `T` and [`JsValue`].
+    ///
+    /// This method should be used whenever [`Writable::new`] cannot be,
+    /// due to lacking trait compatibility.
+    pub fn new_mapped<F, G>(
+        initial_value: T,
+        mapping_fn: F,
+        from_js: G,
+    ) -> Self
+    where
+        F: FnMut(&T) -> JsValue + 'static,
+        G: FnMut(JsValue) -> T + 'static,
+    {
+        Self::init_store(
+            Box::new(UnsafeCell::new(initial_value)),
+            mapping_fn,
+            from_js,
+        )
+    }
+
+    /// Sets the value of the store, notifying all store
+    /// listeners the value has changed.
+    pub fn set(&mut self, new_value: T) {
+        // SAFETY:
+        // This is safe because this function is the only Rust-initiated
+        // way to mutate T, which already requires an &mut Self, and the
+        // `updating` flag prevents the subscription callback from also
+        // writing to `value` while this call is in flight.
+        let value = unsafe { &mut *self.value.get() };
+
+        *value = new_value;
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.updating.set(true);
+            self.writable_store.set((self.mapped_set_fn)(value));
+            self.updating.set(false);
+        }
+    }
+
+    /// Calls the provided `f` with a `&mut T`, returning
+    /// whatever `f` returns. After this function is called,
+    /// the store will be updated and all store listeners will
+    /// be notified.
+    pub fn set_with<F, O>(&mut self, f: F) -> O
+    where
+        F: FnOnce(&mut T) -> O,
+    {
+        // SAFETY:
+        // See `Writable::set`.
+        let value = unsafe { &mut *self.value.get() };
+
+        #[allow(clippy::let_and_return)]
+        let o = f(value);
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.updating.set(true);
+            self.writable_store.set((self.mapped_set_fn)(value));
+            self.updating.set(false);
+        }
+
+        o
+    }
+
+    /// Gets the store that can be directly passed to JS, subscribed to,
+    /// and written to; edits made from JS are synced back into the
+    /// value read through Rust's [`Deref`].
+    pub fn get_store(&self) -> JsValue {
+        #[cfg(not(target_arch = "wasm32"))]
+        panic!(
+            "`Writable::get_store()` can only be called \
+             within `wasm32` targets"
+        );
+
+        #[cfg(target_arch = "wasm32")]
+        return self.writable_store.clone();
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<T> Drop for Writable<T> {
+    fn drop(&mut self) {
+        // Unsubscribe before the value and closure are torn down so JS
+        // can never invoke a closure that has since been dropped.
+        let _ = self.unsubscribe.call0(&JsValue::NULL);
+    }
+}
+
+/// Sound, shared-ownership sibling of [`Readable`].
+///
+/// [`Readable`]'s transparent [`Deref`] is only sound because exactly
+/// one instance of it can exist at a time; `SharedReadable` instead
+/// backs its value with [`Rc<RefCell<T>>`](RefCell), so it is `Clone`
+/// and reads/writes are checked by `RefCell` at runtime instead of
+/// relying on that invariant. Prefer [`Readable`] when you don't need
+/// multiple owners; reach for `SharedReadable` when you do.
+pub struct SharedReadable<T> {
+    value: Rc<RefCell<T>>,
+    #[cfg(target_arch = "wasm32")]
+    writable_store: bindings::Writable,
+    #[cfg(target_arch = "wasm32")]
+    derived_store: bindings::Readable,
+    #[allow(clippy::type_complexity)]
+    #[cfg(target_arch = "wasm32")]
+    mapped_set_fn: Rc<RefCell<dyn FnMut(&T) -> JsValue>>,
+    #[cfg(target_arch = "wasm32")]
+    _derived_store_map_fn: Rc<Closure<dyn FnMut(JsValue) -> JsValue>>,
+}
+
+impl<T> Clone for SharedReadable<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Rc::clone(&self.value),
+            #[cfg(target_arch = "wasm32")]
+            writable_store: self.writable_store.clone(),
+            #[cfg(target_arch = "wasm32")]
+            derived_store: self.derived_store.clone(),
+            #[cfg(target_arch = "wasm32")]
+            mapped_set_fn: Rc::clone(&self.mapped_set_fn),
+            #[cfg(target_arch = "wasm32")]
+            _derived_store_map_fn: Rc::clone(&self._derived_store_map_fn),
+        }
+    }
+}
+
+impl<T> fmt::Debug for SharedReadable<T>
+where
+    T: fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedReadable")
+            .field(&*self.borrow())
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for SharedReadable<T>
+where
+    T: fmt::Display + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.borrow().fmt(f)
+    }
+}
+
+impl<T> Default for SharedReadable<T>
+where
+    T: Default + Clone + Into<JsValue> + 'static,
+{
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: 'static> SharedReadable<T> {
+    #[allow(unused_variables)]
+    fn init_store<F>(initial_value: T, mapping_fn: F) -> Self
+    where
+        F: FnMut(&T) -> JsValue + 'static,
+    {
+        let value = Rc::new(RefCell::new(initial_value));
+
+        #[cfg(target_arch = "wasm32")]
+        let this = {
+            let mapped_set_fn = Rc::new(RefCell::new(mapping_fn))
+                as Rc<RefCell<dyn FnMut(&T) -> JsValue>>;
+
+            let writable_store = bindings::writable(
+                (mapped_set_fn.borrow_mut())(&value.borrow()),
+            );
+
+            let derived_store_map_fn = Closure::new(|v| v);
+
+            let derived_store =
+                bindings::derived(&writable_store, &derived_store_map_fn);
+
+            Self {
+                value,
+                writable_store,
+                derived_store,
+                mapped_set_fn,
+                _derived_store_map_fn: Rc::new(derived_store_map_fn),
+            }
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let this = Self { value };
+
+        this
+    }
+
+    /// Creates a `SharedReadable` Svelte store.
+    ///
+    /// See [`Readable::new`] for the trait requirements this mirrors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svelte_store::SharedReadable;
+    ///
+    /// let a = SharedReadable::new(0u32);
+    /// let b = a.clone();
+    ///
+    /// a.set(1);
+    ///
+    /// assert_eq!(*b.borrow(), 1);
+    /// ```
+    pub fn new(initial_value: T) -> Self
+    where
+        T: Clone + Into<JsValue>,
+    {
+        Self::init_store(initial_value, |v| v.clone().into())
+    }
+
+    /// Creates a new `SharedReadable` Svelte store which calls its
+    /// mapping fn each time the store is set, to produce a [`JsValue`].
+    ///
+    /// See [`Readable::new_mapped`] for when to prefer this over
+    /// [`SharedReadable::new`].
+    pub fn new_mapped<F>(initial_value: T, mapping_fn: F) -> Self
+    where
+        F: FnMut(&T) -> JsValue + 'static,
+    {
+        Self::init_store(initial_value, mapping_fn)
+    }
+
+    /// Borrows the current value of the store.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the store is already mutably borrowed, i.e. from
+    /// within a [`SharedReadable::set_with`] call on another clone of
+    /// this same store.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.value.borrow()
+    }
+
+    /// Sets the value of the store, notifying all store
+    /// listeners the value has changed.
+    pub fn set(&self, new_value: T) {
+        *self.value.borrow_mut() = new_value;
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let value = self.value.borrow();
+            self.writable_store
+                .set((self.mapped_set_fn.borrow_mut())(&value));
+        }
+    }
+
+    /// Calls the provided `f` with a `&mut T`, returning
+    /// whatever `f` returns. After this function is called,
+    /// the store will be updated and all store listeners will
+    /// be notified.
+    pub fn set_with<F, O>(&self, f: F) -> O
+    where
+        F: FnOnce(&mut T) -> O,
+    {
+        let o = f(&mut self.value.borrow_mut());
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let value = self.value.borrow();
+            self.writable_store
+                .set((self.mapped_set_fn.borrow_mut())(&value));
+        }
+
+        o
+    }
+
+    /// Gets the store that can be directly passed to JS and subscribed
+    /// to. See [`Readable::get_store`] for a full example.
+    pub fn get_store(&self) -> JsValue {
+        #[cfg(not(target_arch = "wasm32"))]
+        panic!(
+            "`SharedReadable::get_store()` can only be called \
+             within `wasm32` targets"
+        );
+
+        #[cfg(target_arch = "wasm32")]
+        return self.derived_store.clone();
+    }
 }